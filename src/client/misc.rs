@@ -15,4 +15,34 @@ impl App {
         self.game_state.kinematic_projectiles.clear();
         self.kbot_gpu.update_instance(&[], &self.gpu.device);
     }
+
+    /// Starts watching the asset directory for changes, so edited models,
+    /// textures, and maps hot-reload instead of requiring a restart.
+    pub fn start_asset_watch(&mut self, assets_root: std::path::PathBuf) {
+        self.asset_watch_rx = Some(utils::FileTree::watch(assets_root));
+    }
+
+    /// Drains pending filesystem events and hot-reloads the affected
+    /// assets. Called once per frame from the main loop.
+    pub fn poll_asset_watch(&mut self) {
+        let events: Vec<_> = match &self.asset_watch_rx {
+            Some(rx) => rx.try_iter().collect(),
+            None => return,
+        };
+
+        for event in events {
+            self.asset_tree.patch(&event);
+
+            let path = match &event {
+                utils::FileTreeEvent::Created(path) | utils::FileTreeEvent::Modified(path) => path,
+                utils::FileTreeEvent::Removed(_) => continue,
+            };
+
+            if path.extension().and_then(|ext| ext.to_str()) == Some("png") {
+                log::debug!("hot-reloading texture {:?}", path);
+                let image = utils::ImageRGBA8::open(path.to_str().unwrap());
+                self.kbot_gpu.update_texture(&image, &self.gpu.device);
+            }
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,4 @@
+pub mod client;
+pub mod misc;
+pub mod net;
+pub mod svg_export;
@@ -0,0 +1,93 @@
+use super::client::*;
+use crate::game_state::{GameState, Kbot, Order, Player};
+use crate::utils::{rand_id, Id};
+use crate::*;
+use std::net::SocketAddr;
+
+/// A player-issued mutation addressed to a transport instead of applied
+/// directly to `game_state` — the same order/spawn commands the local
+/// client applies today.
+#[derive(Clone, Debug)]
+pub enum Cmd {
+    Order { kbot: Id<Kbot>, order: Order },
+    Spawn { player: Id<Player>, kbot: Kbot },
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SyncAck {
+    pub tick: u64,
+}
+
+/// Sends a command and blocks until the server has acknowledged it and
+/// echoed back the authoritative tick it landed on, retrying and
+/// re-sending on dropped frames. Used for lobby/setup traffic where the
+/// caller needs to know the command took effect before moving on.
+pub trait SyncClient {
+    fn send_sync(&mut self, cmd: Cmd) -> SyncAck;
+}
+
+/// Fires a command at the server and returns immediately without waiting
+/// for confirmation. Used for per-tick unit orders, where losing an
+/// occasional frame is cheaper than blocking on an ack.
+pub trait AsyncClient {
+    fn send_async(&mut self, cmd: Cmd);
+}
+
+/// Either a loopback single-player client or a remote networked one,
+/// driven behind the same interface by `App`.
+pub trait Client: SyncClient + AsyncClient {
+    fn server_addr(&self) -> SocketAddr;
+}
+
+/// Drives a `Client` against an in-process `GameState` it owns outright,
+/// applying each command immediately and acking on the current tick —
+/// the single-player counterpart to a remote, networked `Client` behind
+/// the same interface.
+pub struct LoopbackClient {
+    addr: SocketAddr,
+    tick: u64,
+    game_state: GameState,
+}
+
+impl LoopbackClient {
+    pub fn new(game_state: GameState) -> Self {
+        LoopbackClient {
+            addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+            tick: 0,
+            game_state,
+        }
+    }
+
+    fn apply(&mut self, cmd: Cmd) {
+        match cmd {
+            Cmd::Order { kbot, order } => {
+                if let Some(kbot) = self.game_state.kbots.get_mut(&kbot) {
+                    kbot.order = Some(order);
+                }
+            }
+            Cmd::Spawn { kbot, .. } => {
+                self.game_state.kbots.insert(rand_id(), kbot);
+            }
+        }
+    }
+}
+
+impl SyncClient for LoopbackClient {
+    fn send_sync(&mut self, cmd: Cmd) -> SyncAck {
+        self.apply(cmd);
+        self.tick += 1;
+        SyncAck { tick: self.tick }
+    }
+}
+
+impl AsyncClient for LoopbackClient {
+    fn send_async(&mut self, cmd: Cmd) {
+        self.apply(cmd);
+    }
+}
+
+impl Client for LoopbackClient {
+    fn server_addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
@@ -0,0 +1,117 @@
+use super::client::*;
+use crate::game_state::{GameState, Player};
+use crate::utils::Id;
+use crate::*;
+use imgui::Ui;
+use na::{Matrix4, Vector2, Vector3};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use utils::face_towards_dir;
+
+impl App {
+    /// Draws the "Export SVG" button in the debug overlay; writes a
+    /// snapshot to `debug_svg_export_<tick>.svg` in the working directory
+    /// when clicked.
+    pub fn debug_ui_svg_export(&self, ui: &Ui, tick: u64) {
+        if ui.button("Export SVG") {
+            let path = std::path::PathBuf::from(format!("debug_svg_export_{}.svg", tick));
+            if let Err(err) = self.export_svg(&path) {
+                log::error!("failed to export SVG snapshot to {:?}: {}", path, err);
+            }
+        }
+    }
+
+    /// Walks the current `game_state` and writes a standalone SVG snapshot:
+    /// kbot positions/orientations, projectile tracks, and selection
+    /// rectangles over the map bounds. Triggered from the imgui debug UI
+    /// for bug reports and AI-path analysis.
+    pub fn export_svg(&self, path: &Path) -> io::Result<()> {
+        let mut svg = String::new();
+        let map_size = self.game_state.map_size;
+        svg_header(&mut svg, map_size.x, map_size.y);
+
+        for kbot in self.game_state.kbots.values() {
+            let color = player_color(&self.game_state, kbot.player_id);
+            let transform = face_towards_dir(&kbot.pos, &kbot.dir, &Vector3::z());
+            svg_kbot(&mut svg, &transform, color);
+        }
+
+        for projectile in self.game_state.kinematic_projectiles.values() {
+            svg_polyline(&mut svg, &projectile.track, "#ffffff");
+        }
+
+        for id in &self.game_state.selected {
+            if let Some(kbot) = self.game_state.kbots.get(id) {
+                svg_selection_rect(&mut svg, kbot.pos.x, kbot.pos.y);
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        fs::write(path, svg)
+    }
+}
+
+fn svg_header(svg: &mut String, w: f32, h: f32) {
+    svg.push_str(&format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\">\n",
+        w, h
+    ));
+    svg.push_str(&format!(
+        "<rect x=\"0\" y=\"0\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"#888888\"/>\n",
+        w, h
+    ));
+}
+
+fn svg_kbot(svg: &mut String, transform: &Matrix4<f32>, color: &str) {
+    let pos = transform.column(3);
+    svg.push_str(&format!(
+        "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"1\" fill=\"{}\"/>\n",
+        pos.x, pos.y, color
+    ));
+
+    let dir = transform.column(0);
+    svg.push_str(&format!(
+        "<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" stroke=\"{}\"/>\n",
+        pos.x,
+        pos.y,
+        pos.x + dir.x,
+        pos.y + dir.y,
+        color
+    ));
+}
+
+fn svg_polyline(svg: &mut String, points: &[Vector2<f32>], color: &str) {
+    let pts = points
+        .iter()
+        .map(|p| format!("{:.2},{:.2}", p.x, p.y))
+        .collect::<Vec<_>>()
+        .join(" ");
+    svg.push_str(&format!(
+        "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\"/>\n",
+        pts, color
+    ));
+}
+
+fn svg_selection_rect(svg: &mut String, x: f32, y: f32) {
+    svg.push_str(&format!(
+        "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"2\" height=\"2\" fill=\"none\" stroke=\"#00ff00\"/>\n",
+        x - 1.0,
+        y - 1.0
+    ));
+}
+
+fn player_color(game_state: &GameState, player_id: Id<Player>) -> &'static str {
+    match game_state
+        .players
+        .get(&player_id)
+        .map(|p| p.color_index)
+        .unwrap_or(0)
+    {
+        0 => "#ff4444",
+        1 => "#4488ff",
+        2 => "#44ff88",
+        _ => "#ffcc00",
+    }
+}
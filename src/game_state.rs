@@ -0,0 +1,36 @@
+use crate::utils::Id;
+use na::{Vector2, Vector3};
+use std::collections::{HashMap, HashSet};
+
+pub struct GameState {
+    pub players: HashMap<Id<Player>, Player>,
+    pub my_player_id: Option<Id<Player>>,
+    pub kbots: HashMap<Id<Kbot>, Kbot>,
+    pub selected: HashSet<Id<Kbot>>,
+    pub kinematic_projectiles: HashMap<Id<Projectile>, Projectile>,
+    pub map_size: Vector2<f32>,
+}
+
+pub struct Player {
+    pub color_index: u8,
+}
+
+pub struct Kbot {
+    pub player_id: Id<Player>,
+    pub pos: Vector3<f32>,
+    pub dir: Vector3<f32>,
+    pub order: Option<Order>,
+}
+
+pub struct Projectile {
+    pub track: Vec<Vector2<f32>>,
+}
+
+/// A standing order a kbot carries out over subsequent ticks, set by an
+/// order/spawn `Cmd` applied to its owning `GameState`.
+#[derive(Clone, Copy, Debug)]
+pub enum Order {
+    Move(Vector2<f32>),
+    Attack(Id<Kbot>),
+    Stop,
+}
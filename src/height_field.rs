@@ -0,0 +1,168 @@
+//! O(1) range-max queries over heightmap terrain, used for frustum/
+//! occlusion culling, placement validity, and artillery line-of-sight.
+
+/// A 2D sparse table over a heightmap, answering max-height queries over
+/// any axis-aligned rectangle in O(1) after an `O(w*h*log(w)*log(h))`
+/// precompute.
+///
+/// The table is immutable once built; terrain that can be edited after
+/// construction needs a rebuild (or a segment tree instead).
+pub struct HeightField {
+    w: usize,
+    h: usize,
+    log_w: usize,
+    log_h: usize,
+    // table[kx][ky][y * w + x] = max over the 2^kx by 2^ky block starting at (x, y)
+    table: Vec<Vec<Vec<f32>>>,
+}
+
+impl HeightField {
+    pub fn build(heights: &[f32], w: usize, h: usize) -> HeightField {
+        assert_eq!(heights.len(), w * h);
+
+        let log_w = log2_floor(w.max(1)) + 1;
+        let log_h = log2_floor(h.max(1)) + 1;
+
+        let mut table = vec![vec![Vec::new(); log_h]; log_w];
+        table[0][0] = heights.to_vec();
+
+        // Grow along x first, combining horizontally adjacent blocks.
+        for kx in 1..log_w {
+            let half = 1usize << (kx - 1);
+            let mut cur = vec![0.0; w * h];
+            for y in 0..h {
+                for x in 0..w {
+                    let right = (x + half).min(w - 1);
+                    cur[y * w + x] = table[kx - 1][0][y * w + x].max(table[kx - 1][0][y * w + right]);
+                }
+            }
+            table[kx][0] = cur;
+        }
+
+        // Then grow along y for every kx, combining vertically adjacent blocks.
+        for kx in 0..log_w {
+            for ky in 1..log_h {
+                let half = 1usize << (ky - 1);
+                let mut cur = vec![0.0; w * h];
+                for y in 0..h {
+                    let down = (y + half).min(h - 1);
+                    for x in 0..w {
+                        let a = table[kx][ky - 1][y * w + x];
+                        let b = table[kx][ky - 1][down * w + x];
+                        cur[y * w + x] = a.max(b);
+                    }
+                }
+                table[kx][ky] = cur;
+            }
+        }
+
+        HeightField {
+            w,
+            h,
+            log_w,
+            log_h,
+            table,
+        }
+    }
+
+    /// Maximum terrain height over `[x0, x1) x [y0, y1)`, clamped to the
+    /// field's bounds.
+    pub fn max_in_rect(&self, x0: usize, y0: usize, x1: usize, y1: usize) -> f32 {
+        let x1 = x1.min(self.w);
+        let y1 = y1.min(self.h);
+        if x0 >= x1 || y0 >= y1 {
+            return f32::NEG_INFINITY;
+        }
+
+        let kx = log2_floor((x1 - x0).max(1)).min(self.log_w - 1);
+        let ky = log2_floor((y1 - y0).max(1)).min(self.log_h - 1);
+        let bx = x1 - (1 << kx);
+        let by = y1 - (1 << ky);
+
+        let t = &self.table[kx][ky];
+        t[y0 * self.w + x0]
+            .max(t[y0 * self.w + bx])
+            .max(t[by * self.w + x0])
+            .max(t[by * self.w + bx])
+    }
+
+    /// Samples the bounding cells of a ray's footprint and returns the
+    /// maximum terrain height it passes over, for a coarse clearance check
+    /// before a precise raycast.
+    pub fn ray_clearance(&self, x0: f32, y0: f32, x1: f32, y1: f32) -> f32 {
+        let min_x = x0.min(x1).floor().max(0.0) as usize;
+        let min_y = y0.min(y1).floor().max(0.0) as usize;
+        let max_x = x0.max(x1).floor() as usize;
+        let max_y = y0.max(y1).floor() as usize;
+        self.max_in_rect(min_x, min_y, max_x + 1, max_y + 1)
+    }
+}
+
+fn log2_floor(x: usize) -> usize {
+    assert!(x >= 1);
+    (usize::BITS - 1 - x.leading_zeros()) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_max_in_rect(heights: &[f32], w: usize, x0: usize, y0: usize, x1: usize, y1: usize) -> f32 {
+        let mut max = f32::NEG_INFINITY;
+        for y in y0..y1 {
+            for x in x0..x1 {
+                max = max.max(heights[y * w + x]);
+            }
+        }
+        max
+    }
+
+    #[test]
+    fn matches_brute_force_on_non_power_of_two_grid() {
+        let w = 7;
+        let h = 5;
+        let heights: Vec<f32> = (0..w * h).map(|i| (i * 37 % 101) as f32).collect();
+        let field = HeightField::build(&heights, w, h);
+
+        for y0 in 0..h {
+            for x0 in 0..w {
+                for y1 in (y0 + 1)..=h {
+                    for x1 in (x0 + 1)..=w {
+                        let expected = brute_max_in_rect(&heights, w, x0, y0, x1, y1);
+                        assert_eq!(field.max_in_rect(x0, y0, x1, y1), expected);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn single_cell_rect_returns_that_cell() {
+        let heights = vec![1.0, 2.0, 3.0, 4.0];
+        let field = HeightField::build(&heights, 2, 2);
+        assert_eq!(field.max_in_rect(1, 0, 2, 1), 2.0);
+        assert_eq!(field.max_in_rect(1, 1, 2, 2), 4.0);
+    }
+
+    #[test]
+    fn rect_clamps_to_field_bounds() {
+        let heights = vec![1.0, 2.0, 3.0, 4.0];
+        let field = HeightField::build(&heights, 2, 2);
+        assert_eq!(field.max_in_rect(0, 0, 100, 100), 4.0);
+    }
+
+    #[test]
+    fn empty_rect_returns_neg_infinity() {
+        let heights = vec![1.0, 2.0, 3.0, 4.0];
+        let field = HeightField::build(&heights, 2, 2);
+        assert_eq!(field.max_in_rect(1, 1, 1, 1), f32::NEG_INFINITY);
+    }
+
+    #[test]
+    fn ray_clearance_covers_the_rays_bounding_cells() {
+        let heights = vec![0.0, 0.0, 0.0, 0.0, 9.0, 0.0, 0.0, 0.0, 0.0];
+        let field = HeightField::build(&heights, 3, 3);
+        assert_eq!(field.ray_clearance(0.0, 0.0, 2.0, 2.0), 9.0);
+        assert_eq!(field.ray_clearance(0.0, 0.0, 0.4, 0.4), 0.0);
+    }
+}
@@ -0,0 +1,269 @@
+//! Quadtree bitstream codec for 2D fields (heightmaps, fog-of-war masks)
+//! that are mostly uniform over large regions, used for save files and
+//! network snapshots.
+//!
+//! Each node emits one flag bit: `0` means "uniform, followed by the
+//! region's value", `1` means "subdivided, recurse into the four children
+//! in fixed order (top-left, top-right, bottom-left, bottom-right)".
+//! Dimensions are padded up to the next power of two for encoding and
+//! clipped back down on decode; recursion bottoms out at single-cell
+//! leaves.
+
+use bitvec::prelude::*;
+use std::fmt;
+
+pub struct QuadTree;
+
+impl QuadTree {
+    pub fn encode<T: Copy + PartialEq + Encodable>(field: &[T], w: usize, h: usize) -> Vec<u8> {
+        assert_eq!(field.len(), w * h);
+        let size = next_pow2(w.max(h));
+
+        let mut bits: BitVec<u8, Msb0> = BitVec::new();
+        encode_node(field, w, h, 0, 0, size, &mut bits);
+        bits.into_vec()
+    }
+
+    /// Decodes a grid written by `encode`. Since this bitstream can come
+    /// from a save file or the network, a buffer that ends early or splits
+    /// a node below a single cell returns `DecodeError` instead of
+    /// panicking. Leaf values are not checked.
+    pub fn decode<T: Copy + Encodable>(
+        reader: &mut BitReader<'_>,
+        w: usize,
+        h: usize,
+    ) -> Result<Vec<T>, DecodeError> {
+        let size = next_pow2(w.max(h));
+        let mut out = vec![T::default_value(); w * h];
+        decode_node(reader, &mut out, w, h, 0, 0, size)?;
+        Ok(out)
+    }
+}
+
+/// The bitstream ended before the encoded grid was fully read, or split a
+/// node below a single cell.
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeError;
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "quadtree bitstream is truncated or malformed")
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+fn encode_node<T: Copy + PartialEq + Encodable>(
+    field: &[T],
+    w: usize,
+    h: usize,
+    x: usize,
+    y: usize,
+    size: usize,
+    bits: &mut BitVec<u8, Msb0>,
+) {
+    if let Some(value) = uniform_value(field, w, h, x, y, size) {
+        bits.push(false);
+        value.write_bits(bits);
+        return;
+    }
+
+    bits.push(true);
+    let half = size / 2;
+    encode_node(field, w, h, x, y, half, bits);
+    encode_node(field, w, h, x + half, y, half, bits);
+    encode_node(field, w, h, x, y + half, half, bits);
+    encode_node(field, w, h, x + half, y + half, half, bits);
+}
+
+fn decode_node<T: Copy + Encodable>(
+    reader: &mut BitReader<'_>,
+    out: &mut [T],
+    w: usize,
+    h: usize,
+    x: usize,
+    y: usize,
+    size: usize,
+) -> Result<(), DecodeError> {
+    if reader.read_bit()? {
+        // The encoder never splits a single cell.
+        if size <= 1 {
+            return Err(DecodeError);
+        }
+        let half = size / 2;
+        decode_node(reader, out, w, h, x, y, half)?;
+        decode_node(reader, out, w, h, x + half, y, half)?;
+        decode_node(reader, out, w, h, x, y + half, half)?;
+        decode_node(reader, out, w, h, x + half, y + half, half)?;
+    } else {
+        let value = T::read_bits(reader)?;
+        fill_region(out, w, h, x, y, size, value);
+    }
+    Ok(())
+}
+
+fn uniform_value<T: Copy + PartialEq + Encodable>(
+    field: &[T],
+    w: usize,
+    h: usize,
+    x: usize,
+    y: usize,
+    size: usize,
+) -> Option<T> {
+    let mut value: Option<T> = None;
+    for dy in 0..size {
+        for dx in 0..size {
+            let cell = cell_value(field, w, h, x + dx, y + dy);
+            match value {
+                None => value = Some(cell),
+                Some(v) if v != cell => return None,
+                _ => {}
+            }
+        }
+    }
+    value
+}
+
+fn cell_value<T: Copy + Encodable>(field: &[T], w: usize, h: usize, x: usize, y: usize) -> T {
+    if x < w && y < h {
+        field[y * w + x]
+    } else {
+        T::default_value()
+    }
+}
+
+fn fill_region<T: Copy>(out: &mut [T], w: usize, h: usize, x: usize, y: usize, size: usize, value: T) {
+    for dy in 0..size {
+        let yy = y + dy;
+        if yy >= h {
+            break;
+        }
+        for dx in 0..size {
+            let xx = x + dx;
+            if xx >= w {
+                break;
+            }
+            out[yy * w + xx] = value;
+        }
+    }
+}
+
+fn next_pow2(x: usize) -> usize {
+    let mut p = 1;
+    while p < x.max(1) {
+        p *= 2;
+    }
+    p
+}
+
+/// A cursor over a `BitSlice`, reading one bit at a time for `QuadTree::decode`.
+pub struct BitReader<'a> {
+    bits: &'a BitSlice<u8, Msb0>,
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(bits: &'a BitSlice<u8, Msb0>) -> Self {
+        BitReader { bits, pos: 0 }
+    }
+
+    fn read_bit(&mut self) -> Result<bool, DecodeError> {
+        let bit = *self.bits.get(self.pos).ok_or(DecodeError)?;
+        self.pos += 1;
+        Ok(bit)
+    }
+}
+
+/// A value type a `QuadTree` leaf can store: a fixed-width bit encoding
+/// plus a default used to pad non-power-of-two dimensions.
+pub trait Encodable: Sized {
+    fn write_bits(&self, bits: &mut BitVec<u8, Msb0>);
+    fn read_bits(reader: &mut BitReader<'_>) -> Result<Self, DecodeError>;
+    fn default_value() -> Self;
+}
+
+impl Encodable for f32 {
+    fn write_bits(&self, bits: &mut BitVec<u8, Msb0>) {
+        let raw = self.to_bits();
+        for i in (0..32).rev() {
+            bits.push((raw >> i) & 1 == 1);
+        }
+    }
+
+    fn read_bits(reader: &mut BitReader<'_>) -> Result<Self, DecodeError> {
+        let mut raw = 0u32;
+        for _ in 0..32 {
+            raw = (raw << 1) | reader.read_bit()? as u32;
+        }
+        Ok(f32::from_bits(raw))
+    }
+
+    fn default_value() -> Self {
+        0.0
+    }
+}
+
+impl Encodable for u8 {
+    fn write_bits(&self, bits: &mut BitVec<u8, Msb0>) {
+        for i in (0..8).rev() {
+            bits.push((self >> i) & 1 == 1);
+        }
+    }
+
+    fn read_bits(reader: &mut BitReader<'_>) -> Result<Self, DecodeError> {
+        let mut raw = 0u8;
+        for _ in 0..8 {
+            raw = (raw << 1) | reader.read_bit()? as u8;
+        }
+        Ok(raw)
+    }
+
+    fn default_value() -> Self {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<T: Copy + PartialEq + Encodable + std::fmt::Debug>(field: &[T], w: usize, h: usize) {
+        let encoded = QuadTree::encode(field, w, h);
+        let bits = BitSlice::<u8, Msb0>::from_slice(&encoded);
+        let mut reader = BitReader::new(bits);
+        let decoded: Vec<T> = QuadTree::decode(&mut reader, w, h).unwrap();
+        assert_eq!(decoded, field);
+    }
+
+    #[test]
+    fn round_trips_uniform_field() {
+        round_trip(&[5u8; 16], 4, 4);
+    }
+
+    #[test]
+    fn round_trips_checkerboard_field() {
+        let field: Vec<u8> = (0..16).map(|i| (i % 2) as u8).collect();
+        round_trip(&field, 4, 4);
+    }
+
+    #[test]
+    fn round_trips_f32_heights() {
+        let field: Vec<f32> = (0..64).map(|i| (i as f32) * 0.5).collect();
+        round_trip(&field, 8, 8);
+    }
+
+    #[test]
+    fn round_trips_non_power_of_two_dimensions() {
+        let field: Vec<u8> = (0..(5 * 3)).map(|i| if i < 4 { 1 } else { 0 }).collect();
+        round_trip(&field, 5, 3);
+    }
+
+    #[test]
+    fn decode_returns_error_on_truncated_buffer() {
+        let encoded = QuadTree::encode(&[1u8, 2, 3, 4], 2, 2);
+        let truncated = &encoded[..encoded.len().saturating_sub(1)];
+        let bits = BitSlice::<u8, Msb0>::from_slice(truncated);
+        let mut reader = BitReader::new(bits);
+        assert!(QuadTree::decode::<u8>(&mut reader, 2, 2).is_err());
+    }
+}
@@ -0,0 +1,149 @@
+//! Zstd-compressed, seekable save/replay container: a framed sequence of
+//! `GameState` snapshots and command batches with a tail index of frame
+//! offsets, so a viewer can jump to any tick without decompressing the
+//! whole stream.
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+enum FrameKind {
+    Snapshot,
+    Commands,
+}
+
+/// Appends zstd-compressed snapshot/command frames to a replay file,
+/// tracking their offsets so `finish()` can write a tail index.
+pub struct ReplayWriter {
+    file: File,
+    index: Vec<(u64, u64, FrameKind)>, // (tick, offset, kind)
+}
+
+impl ReplayWriter {
+    pub fn create(path: &Path) -> io::Result<ReplayWriter> {
+        Ok(ReplayWriter {
+            file: File::create(path)?,
+            index: Vec::new(),
+        })
+    }
+
+    pub fn push_snapshot<S: Serialize>(&mut self, tick: u64, state: &S) -> io::Result<()> {
+        self.push_frame(tick, FrameKind::Snapshot, state)
+    }
+
+    pub fn push_commands<C: Serialize>(&mut self, tick: u64, cmds: &[C]) -> io::Result<()> {
+        self.push_frame(tick, FrameKind::Commands, &cmds)
+    }
+
+    fn push_frame<S: Serialize>(&mut self, tick: u64, kind: FrameKind, value: &S) -> io::Result<()> {
+        let offset = self.file.stream_position()?;
+        let raw = bincode::serialize(value)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        let compressed = zstd::encode_all(&raw[..], 0)?;
+        self.file.write_all(&(compressed.len() as u64).to_le_bytes())?;
+        self.file.write_all(&compressed)?;
+        self.index.push((tick, offset, kind));
+        Ok(())
+    }
+
+    /// Writes the tail index (tick, offset, frame kind per entry) and
+    /// finalizes the file so a `ReplayReader` can seek without scanning.
+    pub fn finish(mut self) -> io::Result<()> {
+        let index_offset = self.file.stream_position()?;
+        for (tick, offset, kind) in &self.index {
+            self.file.write_all(&tick.to_le_bytes())?;
+            self.file.write_all(&offset.to_le_bytes())?;
+            self.file
+                .write_all(&[matches!(kind, FrameKind::Commands) as u8])?;
+        }
+        self.file.write_all(&(self.index.len() as u64).to_le_bytes())?;
+        self.file.write_all(&index_offset.to_le_bytes())?;
+        Ok(())
+    }
+}
+
+/// Reads a replay written by `ReplayWriter`, decompressing frame-by-frame
+/// into a ring buffer and seeking to the nearest preceding snapshot
+/// before replaying commands forward.
+pub struct ReplayReader {
+    file: File,
+    index: Vec<(u64, u64, bool)>, // (tick, offset, is_commands)
+}
+
+impl ReplayReader {
+    pub fn open(path: &Path) -> io::Result<ReplayReader> {
+        let mut file = File::open(path)?;
+
+        file.seek(SeekFrom::End(-16))?;
+        let mut tail = [0u8; 16];
+        file.read_exact(&mut tail)?;
+        let entries = u64::from_le_bytes(tail[0..8].try_into().unwrap());
+        let index_offset = u64::from_le_bytes(tail[8..16].try_into().unwrap());
+
+        file.seek(SeekFrom::Start(index_offset))?;
+        let mut index = Vec::with_capacity(entries as usize);
+        for _ in 0..entries {
+            let mut entry = [0u8; 17];
+            file.read_exact(&mut entry)?;
+            let tick = u64::from_le_bytes(entry[0..8].try_into().unwrap());
+            let offset = u64::from_le_bytes(entry[8..16].try_into().unwrap());
+            index.push((tick, offset, entry[16] != 0));
+        }
+
+        Ok(ReplayReader { file, index })
+    }
+
+    /// Seeks to the snapshot at or before `tick` and returns its raw bytes
+    /// plus the command frames to replay forward from it, up to `tick`.
+    pub fn seek_to_tick(&mut self, tick: u64) -> io::Result<(Vec<u8>, Vec<Vec<u8>>)> {
+        let snapshot_pos = self
+            .index
+            .iter()
+            .rposition(|(t, _, is_commands)| *t <= tick && !is_commands)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("replay has no snapshot at or before tick {}", tick),
+                )
+            })?;
+
+        let (_, snapshot_offset, _) = self.index[snapshot_pos];
+        let snapshot = self.read_frame_at(snapshot_offset)?;
+
+        let mut commands = Vec::new();
+        for &(t, offset, is_commands) in &self.index[snapshot_pos + 1..] {
+            if t > tick {
+                break;
+            }
+            if is_commands {
+                commands.push(self.read_frame_at(offset)?);
+            }
+        }
+
+        Ok((snapshot, commands))
+    }
+
+    fn read_frame_at(&mut self, offset: u64) -> io::Result<Vec<u8>> {
+        self.file.seek(SeekFrom::Start(offset))?;
+        let mut len_buf = [0u8; 8];
+        self.file.read_exact(&mut len_buf)?;
+        let mut compressed = vec![0u8; u64::from_le_bytes(len_buf) as usize];
+        self.file.read_exact(&mut compressed)?;
+
+        // Stream the block through a small ring buffer rather than
+        // allocating the whole decompressed frame up front.
+        let mut decoder = zstd::Decoder::new(&compressed[..])?;
+        let mut out = Vec::new();
+        let mut ring = [0u8; 8192];
+        loop {
+            let n = decoder.read(&mut ring)?;
+            if n == 0 {
+                break;
+            }
+            out.extend_from_slice(&ring[..n]);
+        }
+        Ok(out)
+    }
+}
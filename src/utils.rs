@@ -164,6 +164,94 @@ impl FileTree {
             FileTree::Leaf { path }
         }
     }
+
+    /// Watches `root` for filesystem changes on a worker thread, returning
+    /// a channel of events. Call `patch()` with each received event to keep
+    /// this tree up to date without a full rescan.
+    pub fn watch(root: PathBuf) -> std::sync::mpsc::Receiver<FileTreeEvent> {
+        use notify::{RecursiveMode, Watcher};
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let (notify_tx, notify_rx) = std::sync::mpsc::channel();
+
+        std::thread::spawn(move || {
+            let mut watcher = notify::recommended_watcher(notify_tx).unwrap();
+            watcher.watch(&root, RecursiveMode::Recursive).unwrap();
+
+            // Keep `watcher` alive for as long as events keep arriving.
+            for res in notify_rx {
+                let event = match res {
+                    Ok(event) => event,
+                    Err(_) => continue,
+                };
+                for path in event.paths {
+                    let mapped = match event.kind {
+                        notify::EventKind::Create(_) => Some(FileTreeEvent::Created(path)),
+                        notify::EventKind::Modify(_) => Some(FileTreeEvent::Modified(path)),
+                        notify::EventKind::Remove(_) => Some(FileTreeEvent::Removed(path)),
+                        _ => None,
+                    };
+                    if let Some(event) = mapped {
+                        if tx.send(event).is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+
+        rx
+    }
+
+    /// Applies a single filesystem event, adding, refreshing, or pruning
+    /// the matching `Node`/`Leaf` in place instead of rebuilding the tree.
+    pub fn patch(&mut self, event: &FileTreeEvent) {
+        match event {
+            FileTreeEvent::Created(path) | FileTreeEvent::Modified(path) => self.insert(path),
+            FileTreeEvent::Removed(path) => self.remove(path),
+        }
+    }
+
+    fn insert(&mut self, path: &Path) {
+        if let FileTree::Node { path: my_path, children } = self {
+            if path.parent() == Some(my_path.as_path()) {
+                children.retain(|c| c.path() != path);
+                children.push(FileTree::new(path.to_owned()));
+                return;
+            }
+            for child in children.iter_mut() {
+                if path.starts_with(child.path()) {
+                    child.insert(path);
+                    return;
+                }
+            }
+        }
+    }
+
+    fn remove(&mut self, path: &Path) {
+        if let FileTree::Node { children, .. } = self {
+            children.retain(|c| c.path() != path);
+            for child in children.iter_mut() {
+                child.remove(path);
+            }
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        match self {
+            FileTree::Node { path, .. } => path,
+            FileTree::Leaf { path } => path,
+            FileTree::Unknown => Path::new(""),
+        }
+    }
+}
+
+/// A filesystem change reported by `FileTree::watch()`.
+#[derive(Clone, Debug)]
+pub enum FileTreeEvent {
+    Created(PathBuf),
+    Modified(PathBuf),
+    Removed(PathBuf),
 }
 
 pub struct ImageRGBA8 {
@@ -202,3 +290,132 @@ impl ImageRGBA8 {
         }
     }
 }
+
+/// Number of decoded frames kept resident in RAM at once (triple-buffered:
+/// the frame on screen, the one being decoded, and one in flight between
+/// them).
+const ANIMATION_FRAME_BUFFER: usize = 3;
+
+/// A multi-frame (e.g. APNG) source decoded on a background thread so the
+/// render thread never blocks on PNG decoding.
+///
+/// The first pass through the animation decodes each frame from the PNG
+/// and also appends it, uncompressed, to a scratch file on disk. Once the
+/// animation has looped once, later passes rewind and stream frames back
+/// from the scratch file instead of re-decoding, so looping is cheap on
+/// CPU while RAM stays bounded to `ANIMATION_FRAME_BUFFER` frames.
+pub struct AnimatedImageRGBA8 {
+    pub w: u32,
+    pub h: u32,
+    rx: std::sync::mpsc::Receiver<(ImageRGBA8, std::time::Duration)>,
+    current: ImageRGBA8,
+    next_advance: std::time::Instant,
+}
+
+impl AnimatedImageRGBA8 {
+    pub fn open(path: &str) -> AnimatedImageRGBA8 {
+        // The frame on screen and the one held by the decoder while it waits
+        // to send are outside the channel, so it queues the rest.
+        let (tx, rx) = std::sync::mpsc::sync_channel(ANIMATION_FRAME_BUFFER - 2);
+        let path = path.to_owned();
+        std::thread::spawn(move || Self::decode_loop(path, tx));
+
+        // Block for the first frame so callers always have something to
+        // draw; everything after this runs off the background thread.
+        let (current, delay) = rx.recv().unwrap();
+
+        AnimatedImageRGBA8 {
+            w: current.w,
+            h: current.h,
+            rx,
+            current,
+            next_advance: std::time::Instant::now() + delay,
+        }
+    }
+
+    /// Advances to the next frame once its delay has elapsed and returns
+    /// the frame the render loop should currently draw. Call once per tick.
+    pub fn poll_frame(&mut self) -> &ImageRGBA8 {
+        let now = std::time::Instant::now();
+        if now >= self.next_advance {
+            if let Ok((frame, delay)) = self.rx.try_recv() {
+                self.current = frame;
+                self.next_advance = now + delay;
+            }
+        }
+        &self.current
+    }
+
+    fn decode_loop(path: String, tx: std::sync::mpsc::SyncSender<(ImageRGBA8, std::time::Duration)>) {
+        use std::fs::File;
+        use std::io::{Read, Seek, SeekFrom, Write};
+
+        let scratch_path = std::env::temp_dir().join(format!("{}.rgba8-frames", rand_id_unsafe()));
+        let mut scratch = File::create(&scratch_path).unwrap();
+        let mut delays = Vec::new();
+
+        // First pass: decode from the PNG, sending each frame on as it's
+        // produced and caching it to the scratch file for later loops.
+        // `frame_size` always comes from `info.buffer_size()`, matching
+        // `ImageRGBA8::open`, since it depends on the PNG's actual bit
+        // depth/color type and isn't always `w * h * 4`.
+        let (w, h, frame_size) = {
+            let mut decoder = png::Decoder::new(File::open(&path).unwrap());
+            decoder.set_transformations(png::Transformations::IDENTITY);
+            let (info, mut reader) = decoder.read_info().unwrap();
+            let w = info.width;
+            let h = info.height;
+            let frame_size = info.buffer_size();
+
+            loop {
+                let mut buf = vec![0; frame_size];
+                match reader.next_frame(&mut buf) {
+                    Ok(_) => {
+                        let delay = reader
+                            .info()
+                            .frame_control()
+                            .map(|fc| {
+                                std::time::Duration::from_secs_f32(
+                                    fc.delay_num as f32 / fc.delay_den.max(1) as f32,
+                                )
+                            })
+                            .unwrap_or_else(|| std::time::Duration::from_millis(100));
+
+                        scratch.write_all(&buf).unwrap();
+                        delays.push(delay);
+                        if tx.send((ImageRGBA8 { w, h, data: buf }, delay)).is_err() {
+                            let _ = std::fs::remove_file(&scratch_path);
+                            return;
+                        }
+                    }
+                    Err(_) => break,
+                }
+            }
+
+            (w, h, frame_size)
+        };
+
+        if delays.is_empty() {
+            let _ = std::fs::remove_file(&scratch_path);
+            return;
+        }
+
+        // Subsequent loops: rewind and stream frames back from the scratch
+        // file instead of re-decoding the PNG.
+        let mut scratch = File::open(&scratch_path).unwrap();
+        loop {
+            scratch.seek(SeekFrom::Start(0)).unwrap();
+            for &delay in &delays {
+                let mut buf = vec![0; frame_size];
+                if scratch.read_exact(&mut buf).is_err() {
+                    let _ = std::fs::remove_file(&scratch_path);
+                    return;
+                }
+                if tx.send((ImageRGBA8 { w, h, data: buf }, delay)).is_err() {
+                    let _ = std::fs::remove_file(&scratch_path);
+                    return;
+                }
+            }
+        }
+    }
+}